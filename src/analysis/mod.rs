@@ -0,0 +1,3 @@
+pub mod dataflow;
+pub mod reentrancy;
+pub mod taint;
@@ -0,0 +1,288 @@
+use std::collections::{HashMap, HashSet};
+
+use cairo_lang_sierra::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType};
+use cairo_lang_sierra::extensions::starknet::StarkNetConcreteLibfunc;
+use cairo_lang_sierra::ids::VarId;
+use cairo_lang_sierra::program::{GenStatement, Invocation, Statement as SierraStatement};
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+
+use crate::analysis::dataflow::Analysis;
+use crate::core::cfg::{Cfg, CfgRegular};
+use crate::core::function::{Function, Type};
+
+/// Tracks which Sierra variables carry untrusted input: function parameters (caller-controlled)
+/// and the result variables of calls made through an `AbiCallContract` trait (data returned by an
+/// untrusted contract). Used to flag when that data reaches a storage write or an external call
+/// without first passing through a sanitizing assert/range-check.
+#[derive(Clone)]
+pub struct TaintAnalysis {
+    /// Variables tainted on entry to the function: its own parameters. `AbiCallContract` results
+    /// are not held here; `transfer` taints them where the call is made.
+    sources: HashSet<VarId>,
+    /// Per-callee summary: does calling this function taint its return values whenever any of
+    /// its arguments is tainted? Computed bottom-up over the call graph by [`build_summaries`].
+    summaries: HashMap<String, bool>,
+}
+
+impl TaintAnalysis {
+    /// `summaries` is the whole-call-graph fixpoint from [`build_summaries`], computed once per
+    /// run and shared across every function's `TaintAnalysis` rather than recomputed here: this
+    /// constructor runs once per `External`/`L1Handler` function, so folding the O(N) summary
+    /// pass into it would make it O(N²) over the whole run.
+    pub fn new(function: &Function, summaries: HashMap<String, bool>) -> Self {
+        let sources = function.params().map(|p| p.id.clone()).collect();
+        TaintAnalysis { sources, summaries }
+    }
+}
+
+/// Libfuncs that sanitize their output: once a tainted value has been range-checked or asserted
+/// against, the value it produces is no longer considered attacker-controlled.
+fn is_sanitizer(libfunc_name: &str) -> bool {
+    libfunc_name.contains("assert")
+        || libfunc_name.contains("is_zero")
+        || libfunc_name.contains("range_check")
+        || libfunc_name.contains("less_than")
+        || libfunc_name.contains("less_equal")
+        || libfunc_name.contains("eq")
+}
+
+/// Compute, for every function, whether a tainted parameter can reach one of its `Return`
+/// operands, folding through summaries of whatever it calls. Iterated to a fixpoint since a
+/// callee's summary may itself depend on a callee that hasn't been visited yet this round
+/// (including the function itself, for recursion): every summary starts at `false` and a full
+/// pass is re-run until none of them change.
+pub(crate) fn build_summaries(
+    functions: &[Function],
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+) -> HashMap<String, bool> {
+    let mut summaries: HashMap<String, bool> =
+        functions.iter().map(|f| (f.name(), false)).collect();
+
+    loop {
+        let mut changed = false;
+        for function in functions {
+            let taints_return = function_taints_return(function, functions, registry, &summaries);
+            let entry = summaries.entry(function.name()).or_insert(false);
+            if taints_return && !*entry {
+                *entry = true;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    summaries
+}
+
+/// Walk `function`'s statements forward from its own tainted parameters, propagating through
+/// invocations the same way [`TaintAnalysis::transfer`] does (calls into functions whose summary
+/// is already known to taint-on-return, or into `AbiCallContract` calls, spread taint to their
+/// results; sanitizers don't), and report whether any tainted variable reaches a `Return`.
+fn function_taints_return(
+    function: &Function,
+    functions: &[Function],
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+    summaries: &HashMap<String, bool>,
+) -> bool {
+    let mut tainted: HashSet<VarId> = function.params().map(|p| p.id.clone()).collect();
+
+    for statement in function.get_statements() {
+        match statement {
+            GenStatement::Invocation(invoc) => {
+                if !invoc.args.iter().any(|a| tainted.contains(a)) {
+                    continue;
+                }
+
+                let Ok(lib_func) = registry.get_libfunc(&invoc.libfunc_id) else {
+                    continue;
+                };
+
+                let propagates = if let CoreConcreteLibfunc::FunctionCall(f_called) = lib_func {
+                    let callee_name =
+                        f_called.function.id.debug_name.as_ref().unwrap().to_string();
+                    let is_abi_call_contract = functions
+                        .iter()
+                        .find(|f| f.name() == callee_name)
+                        .is_some_and(|f| *f.ty() == Type::AbiCallContract);
+                    is_abi_call_contract || *summaries.get(&callee_name).unwrap_or(&false)
+                } else {
+                    let libfunc_name = invoc
+                        .libfunc_id
+                        .debug_name
+                        .as_ref()
+                        .map(|n| n.as_str())
+                        .unwrap_or_default();
+                    !is_sanitizer(libfunc_name)
+                };
+
+                if propagates {
+                    for branch in &invoc.branches {
+                        tainted.extend(branch.results.iter().cloned());
+                    }
+                }
+            }
+            GenStatement::Return(vars) => {
+                if vars.iter().any(|v| tainted.contains(v)) {
+                    return true;
+                }
+            }
+        }
+    }
+
+    false
+}
+
+/// Whether `invoc` is a storage write or an external/library call, the sinks this analysis cares
+/// about: direct `StorageWrite`/`CallContract`/`LibraryCall` syscalls, or a `FunctionCall` into a
+/// compiler-generated storage-write wrapper or an ABI `AbiCallContract`/`AbiLibraryCall` trait
+/// function (the same classification `Function::compute_meta_informations` already does for
+/// `storage_vars_written`/`external_functions_calls`/`library_functions_calls`).
+fn is_sink(
+    invoc: &Invocation,
+    functions: &[Function],
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+) -> bool {
+    match registry.get_libfunc(&invoc.libfunc_id) {
+        Ok(CoreConcreteLibfunc::FunctionCall(f_called)) => {
+            let callee_name = f_called.function.id.debug_name.as_ref().unwrap().to_string();
+            functions
+                .iter()
+                .find(|f| f.name() == callee_name)
+                .is_some_and(|f| match f.ty() {
+                    Type::AbiCallContract | Type::AbiLibraryCall => true,
+                    Type::Storage => callee_name.ends_with("write"),
+                    _ => false,
+                })
+        }
+        Ok(CoreConcreteLibfunc::StarkNet(syscall)) => matches!(
+            syscall,
+            StarkNetConcreteLibfunc::StorageWrite(_)
+                | StarkNetConcreteLibfunc::CallContract(_)
+                | StarkNetConcreteLibfunc::LibraryCall(_)
+        ),
+        _ => false,
+    }
+}
+
+/// Every storage write, external call, or library call whose arguments include a value tainted by
+/// a function parameter or an `AbiCallContract` result, without first passing through a
+/// sanitizing assert/range-check. This is the finding the rest of the module exists to produce:
+/// `TaintAnalysis::transfer` only maintains the per-basic-block tainted-variable lattice, it never
+/// checks that state against anything, so a run would never surface a finding without this. Must
+/// be called after [`Function::run_analyses`] has populated `function.analyses().taint`.
+pub fn tainted_sinks<'a>(
+    function: &'a Function,
+    functions: &[Function],
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+) -> Vec<&'a SierraStatement> {
+    let cfg = function.get_cfg();
+    let mut findings = Vec::new();
+
+    for bb in cfg.get_basic_blocks() {
+        let Some(tainted) = function.analyses().taint.get(&bb.get_id()) else {
+            continue;
+        };
+
+        for instruction in bb.get_instructions() {
+            let GenStatement::Invocation(invoc) = instruction else {
+                continue;
+            };
+            let any_tainted = invoc.args.iter().any(|a| tainted.contains(a));
+            if any_tainted && is_sink(invoc, functions, registry) {
+                findings.push(instruction);
+            }
+        }
+    }
+
+    findings
+}
+
+impl Analysis for TaintAnalysis {
+    type State = HashSet<VarId>;
+
+    fn meet(&self, states: &[Self::State]) -> Self::State {
+        states.iter().fold(HashSet::new(), |mut acc, s| {
+            acc.extend(s.iter().cloned());
+            acc
+        })
+    }
+
+    fn transfer(
+        &self,
+        state: &Self::State,
+        bb: usize,
+        cfg: &CfgRegular,
+        functions: &[Function],
+        registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+    ) -> Self::State {
+        let mut tainted = state.clone();
+        tainted.extend(self.sources.iter().cloned());
+
+        for instruction in cfg.get_basic_block(bb).get_instructions() {
+            if let GenStatement::Invocation(invoc) = instruction {
+                let lib_func = registry
+                    .get_libfunc(&invoc.libfunc_id)
+                    .expect("Library function not found in the registry");
+
+                let any_tainted = invoc.args.iter().any(|a| tainted.contains(a));
+                let all_results = invoc.branches.iter().flat_map(|b| b.results.iter());
+
+                if let CoreConcreteLibfunc::FunctionCall(f_called) = lib_func {
+                    let callee_name = f_called.function.id.debug_name.as_ref().unwrap().to_string();
+                    let is_abi_call_contract = functions
+                        .iter()
+                        .find(|f| f.name() == callee_name)
+                        .is_some_and(|f| *f.ty() == Type::AbiCallContract);
+
+                    if is_abi_call_contract || (any_tainted && *self.summaries.get(&callee_name).unwrap_or(&false)) {
+                        for r in all_results {
+                            tainted.insert(r.clone());
+                        }
+                    }
+                    continue;
+                }
+
+                let libfunc_name = invoc.libfunc_id.debug_name.as_ref().map(|n| n.as_str()).unwrap_or_default();
+                if is_sanitizer(libfunc_name) {
+                    continue;
+                }
+
+                if any_tainted {
+                    for r in all_results {
+                        tainted.insert(r.clone());
+                    }
+                }
+            }
+        }
+
+        tainted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_sanitizer_matches_asserts_and_comparisons() {
+        for name in [
+            "assert_nn",
+            "felt252_is_zero",
+            "u128_range_check",
+            "u8_less_than",
+            "u8_less_equal",
+            "felt252_eq",
+        ] {
+            assert!(is_sanitizer(name), "{name} should be a sanitizer");
+        }
+    }
+
+    #[test]
+    fn is_sanitizer_rejects_unrelated_libfuncs() {
+        for name in ["store_temp", "storage_write_syscall", "array_append"] {
+            assert!(!is_sanitizer(name), "{name} should not be a sanitizer");
+        }
+    }
+}
@@ -0,0 +1,385 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use cairo_lang_sierra::extensions::core::{CoreLibfunc, CoreType};
+use cairo_lang_sierra::program::{GenBranchTarget, GenStatement, Statement as SierraStatement};
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+
+use super::function::Function;
+
+pub trait Cfg {
+    fn get_basic_blocks(&self) -> &Vec<BasicBlock>;
+}
+
+#[derive(Clone, Default)]
+pub struct BasicBlock {
+    id: usize,
+    instructions: Vec<SierraStatement>,
+    outgoing: Vec<usize>,
+    incoming: Vec<usize>,
+}
+
+impl BasicBlock {
+    pub fn get_id(&self) -> usize {
+        self.id
+    }
+
+    pub fn get_instructions(&self) -> &Vec<SierraStatement> {
+        &self.instructions
+    }
+
+    pub fn get_outgoing_basic_blocks(&self) -> &Vec<usize> {
+        &self.outgoing
+    }
+
+    pub fn get_incoming_basic_blocks(&self) -> &Vec<usize> {
+        &self.incoming
+    }
+}
+
+/// A natural loop discovered from a single back edge `tail -> header` (`header` dominates
+/// `tail`): `body` is the header plus every basic block that can reach the tail without going
+/// back through the header, i.e. everything that can run before control returns to the top of the
+/// loop.
+#[derive(Clone, Debug)]
+pub struct Loop {
+    pub header: usize,
+    pub body: HashSet<usize>,
+}
+
+/// A CFG built directly from a function's Sierra statements, one basic block per maximal run of
+/// statements with a single entry and a single exit.
+#[derive(Clone, Default)]
+pub struct CfgRegular {
+    entry: usize,
+    basic_blocks: Vec<BasicBlock>,
+    /// Immediate dominator of every basic block but the entry
+    idom: HashMap<usize, usize>,
+    loops: Vec<Loop>,
+}
+
+impl Cfg for CfgRegular {
+    fn get_basic_blocks(&self) -> &Vec<BasicBlock> {
+        &self.basic_blocks
+    }
+}
+
+impl CfgRegular {
+    pub fn new() -> Self {
+        CfgRegular::default()
+    }
+
+    pub fn get_basic_block(&self, id: usize) -> &BasicBlock {
+        &self.basic_blocks[id]
+    }
+
+    pub fn get_predecessors(&self, id: usize) -> Vec<usize> {
+        self.basic_blocks[id].incoming.clone()
+    }
+
+    pub fn get_successors(&self, id: usize) -> Vec<usize> {
+        self.basic_blocks[id].outgoing.clone()
+    }
+
+    /// `statements` is always this function's own slice starting at its first statement (see
+    /// `Function::analyze`), so the entry block is always block 0 once `build_basic_blocks` has
+    /// numbered blocks `0..n` over that slice — never the raw Sierra program statement index a
+    /// caller might otherwise be tempted to pass in here, which is only meaningful as an offset
+    /// into the *whole* program and would root the dominator tree at the wrong block (or panic)
+    /// for any function that isn't the first one in the program.
+    pub fn analyze(
+        &mut self,
+        statements: &[SierraStatement],
+        _functions: &[Function],
+        _registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+        _name: String,
+    ) {
+        self.entry = 0;
+        self.build_basic_blocks(statements);
+        self.idom = compute_dominators(&self.basic_blocks, self.entry);
+        self.loops = compute_loops(&self.basic_blocks, &self.idom);
+    }
+
+    fn build_basic_blocks(&mut self, statements: &[SierraStatement]) {
+        let mut leaders: BTreeSet<usize> = BTreeSet::new();
+        leaders.insert(0);
+        for (idx, statement) in statements.iter().enumerate() {
+            if let GenStatement::Invocation(invoc) = statement {
+                for branch in &invoc.branches {
+                    if let GenBranchTarget::Statement(target) = branch.target {
+                        leaders.insert(target.0);
+                    }
+                }
+                if invoc.branches.len() > 1 && idx + 1 < statements.len() {
+                    leaders.insert(idx + 1);
+                }
+            } else if idx + 1 < statements.len() {
+                // `GenStatement::Return` also ends a block
+                leaders.insert(idx + 1);
+            }
+        }
+
+        let leaders: Vec<usize> = leaders.into_iter().collect();
+        let leader_to_block: HashMap<usize, usize> = leaders
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| (start, i))
+            .collect();
+
+        let mut blocks: Vec<BasicBlock> = leaders
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| {
+                let end = leaders.get(i + 1).copied().unwrap_or(statements.len());
+                BasicBlock {
+                    id: i,
+                    instructions: statements[start..end].to_vec(),
+                    outgoing: Vec::new(),
+                    incoming: Vec::new(),
+                }
+            })
+            .collect();
+
+        for (i, &start) in leaders.iter().enumerate() {
+            let end = leaders.get(i + 1).copied().unwrap_or(statements.len());
+            let mut successors = match &statements[end - 1] {
+                GenStatement::Invocation(invoc) => invoc
+                    .branches
+                    .iter()
+                    .map(|branch| match branch.target {
+                        GenBranchTarget::Statement(target) => target.0,
+                        GenBranchTarget::Fallthrough => end,
+                    })
+                    .filter_map(|target| leader_to_block.get(&target).copied())
+                    .collect::<Vec<usize>>(),
+                GenStatement::Return(_) => Vec::new(),
+            };
+            // A single-branch invocation that falls through has no explicit branch target: its
+            // successor is just the next block.
+            if successors.is_empty() && end < statements.len() {
+                if let Some(&next) = leader_to_block.get(&end) {
+                    successors.push(next);
+                }
+            }
+            successors.sort_unstable();
+            successors.dedup();
+
+            for &succ in &successors {
+                blocks[succ].incoming.push(i);
+            }
+            blocks[i].outgoing = successors;
+        }
+
+        self.basic_blocks = blocks;
+    }
+
+    /// Immediate dominator of `bb`: the closest basic block that every path from the entry to
+    /// `bb` is guaranteed to pass through. `None` for the entry block itself.
+    pub fn idom(&self, bb: usize) -> Option<usize> {
+        self.idom.get(&bb).copied()
+    }
+
+    /// All dominators of `bb` (including `bb` itself), walking up the dominator tree from `bb` to
+    /// the entry block.
+    pub fn dominators(&self, bb: usize) -> Vec<usize> {
+        let mut doms = vec![bb];
+        let mut current = bb;
+        while let Some(&parent) = self.idom.get(&current) {
+            doms.push(parent);
+            current = parent;
+        }
+        doms
+    }
+
+    /// Natural loops found in this CFG, one per distinct loop header
+    pub fn loops(&self) -> &Vec<Loop> {
+        &self.loops
+    }
+}
+
+/// Cooper-Harvey-Kennedy iterative dominator algorithm: order blocks in reverse postorder, then
+/// repeatedly recompute each block's immediate dominator as the intersection of its already-
+/// processed predecessors' idoms, until nothing changes.
+fn compute_dominators(blocks: &[BasicBlock], entry: usize) -> HashMap<usize, usize> {
+    if blocks.is_empty() {
+        return HashMap::new();
+    }
+
+    let rpo = reverse_postorder(blocks, entry);
+    let rpo_index: HashMap<usize, usize> = rpo.iter().enumerate().map(|(i, &b)| (b, i)).collect();
+
+    let mut idom: HashMap<usize, usize> = HashMap::new();
+    idom.insert(entry, entry);
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &bb in rpo.iter().filter(|&&b| b != entry) {
+            let mut new_idom: Option<usize> = None;
+            for &pred in &blocks[bb].incoming {
+                if !idom.contains_key(&pred) {
+                    continue;
+                }
+                new_idom = Some(match new_idom {
+                    None => pred,
+                    Some(curr) => intersect(curr, pred, &idom, &rpo_index),
+                });
+            }
+            if let Some(new_idom) = new_idom {
+                if idom.get(&bb) != Some(&new_idom) {
+                    idom.insert(bb, new_idom);
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    idom.remove(&entry);
+    idom
+}
+
+fn intersect(
+    mut a: usize,
+    mut b: usize,
+    idom: &HashMap<usize, usize>,
+    rpo_index: &HashMap<usize, usize>,
+) -> usize {
+    while a != b {
+        while rpo_index[&a] > rpo_index[&b] {
+            a = idom[&a];
+        }
+        while rpo_index[&b] > rpo_index[&a] {
+            b = idom[&b];
+        }
+    }
+    a
+}
+
+fn reverse_postorder(blocks: &[BasicBlock], entry: usize) -> Vec<usize> {
+    let mut visited = vec![false; blocks.len()];
+    let mut postorder = Vec::with_capacity(blocks.len());
+    let mut stack = vec![(entry, false)];
+
+    while let Some((node, expanded)) = stack.pop() {
+        if expanded {
+            postorder.push(node);
+            continue;
+        }
+        if visited[node] {
+            continue;
+        }
+        visited[node] = true;
+        stack.push((node, true));
+        for &succ in &blocks[node].outgoing {
+            if !visited[succ] {
+                stack.push((succ, false));
+            }
+        }
+    }
+
+    postorder.reverse();
+    postorder
+}
+
+/// Back-edge identification: an edge `tail -> header` is a back edge when `header` dominates
+/// `tail`. Each back edge's natural loop body is every block that can reach `tail` by walking
+/// predecessors without crossing through `header` again; back edges sharing a header merge into
+/// one `Loop`.
+fn compute_loops(blocks: &[BasicBlock], idom: &HashMap<usize, usize>) -> Vec<Loop> {
+    let dominators_of = |bb: usize| -> HashSet<usize> {
+        let mut doms = HashSet::new();
+        let mut current = bb;
+        doms.insert(current);
+        while let Some(&parent) = idom.get(&current) {
+            if !doms.insert(parent) {
+                break;
+            }
+            current = parent;
+        }
+        doms
+    };
+
+    let mut bodies: HashMap<usize, HashSet<usize>> = HashMap::new();
+    for (tail, block) in blocks.iter().enumerate() {
+        for &header in &block.outgoing {
+            if !dominators_of(tail).contains(&header) {
+                continue;
+            }
+
+            let body = bodies.entry(header).or_default();
+            body.insert(header);
+
+            let mut worklist = vec![tail];
+            while let Some(node) = worklist.pop() {
+                if body.insert(node) {
+                    worklist.extend(blocks[node].incoming.iter().copied());
+                }
+            }
+        }
+    }
+
+    let mut loops: Vec<Loop> = bodies
+        .into_iter()
+        .map(|(header, body)| Loop { header, body })
+        .collect();
+    loops.sort_by_key(|l| l.header);
+    loops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(id: usize, outgoing: &[usize], incoming: &[usize]) -> BasicBlock {
+        BasicBlock {
+            id,
+            instructions: Vec::new(),
+            outgoing: outgoing.to_vec(),
+            incoming: incoming.to_vec(),
+        }
+    }
+
+    #[test]
+    fn dominators_of_diamond_meet_at_entry() {
+        // 0 -> 1, 0 -> 2, 1 -> 3, 2 -> 3
+        let blocks = vec![
+            block(0, &[1, 2], &[]),
+            block(1, &[3], &[0]),
+            block(2, &[3], &[0]),
+            block(3, &[], &[1, 2]),
+        ];
+
+        let idom = compute_dominators(&blocks, 0);
+
+        assert_eq!(idom.get(&1), Some(&0));
+        assert_eq!(idom.get(&2), Some(&0));
+        // 3 is reachable through both branches, so its only dominator above itself is the entry.
+        assert_eq!(idom.get(&3), Some(&0));
+        assert_eq!(idom.get(&0), None);
+    }
+
+    #[test]
+    fn natural_loop_is_found_from_its_back_edge() {
+        // 0 -> 1 -> 2 -> 1 (back edge), 2 -> 3 (loop exit)
+        let blocks = vec![
+            block(0, &[1], &[]),
+            block(1, &[2], &[0, 2]),
+            block(2, &[1, 3], &[1]),
+            block(3, &[], &[2]),
+        ];
+
+        let idom = compute_dominators(&blocks, 0);
+        let loops = compute_loops(&blocks, &idom);
+
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].header, 1);
+        assert_eq!(loops[0].body, HashSet::from([1, 2]));
+    }
+
+    #[test]
+    fn no_loops_in_acyclic_cfg() {
+        let blocks = vec![block(0, &[1, 2], &[]), block(1, &[], &[0]), block(2, &[], &[0])];
+        let idom = compute_dominators(&blocks, 0);
+
+        assert!(compute_loops(&blocks, &idom).is_empty());
+    }
+}
@@ -2,11 +2,14 @@ use std::collections::HashMap;
 use std::io::Write;
 
 use super::cfg::{Cfg, CfgRegular};
+use super::query::{ContentHash, QueryCache};
 use crate::analysis::dataflow::AnalysisState;
 use crate::analysis::dataflow::Engine;
 use crate::analysis::reentrancy::ReentrancyAnalysis;
+use crate::analysis::taint::TaintAnalysis;
 use crate::utils::BUILTINS;
 use cairo_lang_sierra::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType};
+use cairo_lang_sierra::extensions::starknet::StarkNetConcreteLibfunc;
 use cairo_lang_sierra::ids::ConcreteTypeId;
 use cairo_lang_sierra::program::{
     Function as SierraFunction, GenStatement, Param, Statement as SierraStatement,
@@ -20,6 +23,8 @@ use graphviz_rust::printer::{DotPrinter, PrinterContext};
 pub struct Analyses {
     /// Reentrancy info result
     pub reentrancy: HashMap<usize, AnalysisState<ReentrancyAnalysis>>,
+    /// Taint info result: which Sierra variables carry untrusted input at each basic block
+    pub taint: HashMap<usize, AnalysisState<TaintAnalysis>>,
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -59,22 +64,47 @@ pub struct Function {
     statements: Vec<SierraStatement>,
     /// A regular CFG from the statements
     cfg_regular: CfgRegular,
-    /// Storage variables read (NOTE it doesn't have vars read using the syscall directly)
+    /// Storage variables read, including through the `StorageRead` syscall directly
     storage_vars_read: Vec<SierraStatement>,
-    /// Storage variables written (NOTE it doesn't have vars written using the syscall directly)
+    /// Storage variables written, including through the `StorageWrite` syscall directly
     storage_vars_written: Vec<SierraStatement>,
     /// Core functions called
     core_functions_calls: Vec<SierraStatement>,
     /// Private functions called
     private_functions_calls: Vec<SierraStatement>,
-    /// Events emitted (NOTE it doesn't have events emitted using the syscall directly)
+    /// Events emitted, including through the `EmitEvent` syscall directly
     events_emitted: Vec<SierraStatement>,
-    /// External functions called through an ABI trait (NOTE it doesn't have external functions called using the syscall directly)
+    /// External functions called through an ABI trait
     external_functions_calls: Vec<SierraStatement>,
-    /// Library functions called through an ABI trait (NOTE it doesn't have library functions called using the syscall directly)
+    /// Library functions called through an ABI trait
     library_functions_calls: Vec<SierraStatement>,
+    /// External calls made through the raw `CallContract` syscall rather than an ABI trait.
+    /// Kept separate from `external_functions_calls` since they're recovered differently, but
+    /// chained into the same public accessor so detectors see both.
+    raw_external_functions_calls: Vec<SierraStatement>,
+    /// Library calls made through the raw `LibraryCall` syscall rather than an ABI trait. See
+    /// `raw_external_functions_calls`.
+    raw_library_functions_calls: Vec<SierraStatement>,
     /// Analyses results
     analyses: Analyses,
+    /// Content hash this function was last analyzed at, i.e. the key it's stored under in the
+    /// `QueryCache`. `None` until `analyze` has run at least once.
+    content_hash: Option<ContentHash>,
+}
+
+/// The meta-information collections computed by [`Function::compute_meta_informations`], bundled
+/// together so the query cache can store and restore them as a single unit.
+#[derive(Clone, Default)]
+pub(crate) struct MetaInformations {
+    pub storage_vars_read: Vec<SierraStatement>,
+    pub storage_vars_written: Vec<SierraStatement>,
+    pub core_functions_calls: Vec<SierraStatement>,
+    pub private_functions_calls: Vec<SierraStatement>,
+    pub events_emitted: Vec<SierraStatement>,
+    pub external_functions_calls: Vec<SierraStatement>,
+    pub library_functions_calls: Vec<SierraStatement>,
+    pub raw_external_functions_calls: Vec<SierraStatement>,
+    pub raw_library_functions_calls: Vec<SierraStatement>,
 }
 
 impl Function {
@@ -91,7 +121,10 @@ impl Function {
             events_emitted: Vec::new(),
             external_functions_calls: Vec::new(),
             library_functions_calls: Vec::new(),
+            raw_external_functions_calls: Vec::new(),
+            raw_library_functions_calls: Vec::new(),
             analyses: Analyses::default(),
+            content_hash: None,
         }
     }
 
@@ -125,11 +158,15 @@ impl Function {
     }
 
     pub fn external_functions_calls(&self) -> impl Iterator<Item = &SierraStatement> {
-        self.external_functions_calls.iter()
+        self.external_functions_calls
+            .iter()
+            .chain(self.raw_external_functions_calls.iter())
     }
 
     pub fn library_functions_calls(&self) -> impl Iterator<Item = &SierraStatement> {
-        self.library_functions_calls.iter()
+        self.library_functions_calls
+            .iter()
+            .chain(self.raw_library_functions_calls.iter())
     }
 
     pub fn analyses(&self) -> &Analyses {
@@ -175,28 +212,51 @@ impl Function {
         &self.cfg_regular
     }
 
+    /// Build (or reuse from `cache`) this function's CFG and meta-information. Re-running
+    /// Caracal on an edited contract only pays for the functions whose content hash changed:
+    /// its own statements, or transitively, the hash of anything it calls.
+    ///
+    /// `hashes` is looked up rather than recomputed here: [`compute_content_hashes`] walks the
+    /// whole call graph (a Tarjan SCC pass plus re-hashing every function's statements), so
+    /// calling it once per function analyzed would make a cold run O(N) times more expensive than
+    /// it needs to be. The driver computes it once with [`compute_content_hashes`] before looping
+    /// over `functions` and passes the same map into every `analyze` call.
+    ///
+    /// [`compute_content_hashes`]: super::query::compute_content_hashes
     pub fn analyze(
         &mut self,
         functions: &[Function],
         registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+        cache: &mut QueryCache,
+        hashes: &HashMap<String, ContentHash>,
     ) {
-        self.cfg_regular.analyze(
-            &self.statements,
-            self.data.entry_point.0,
-            functions,
-            registry,
-            self.name(),
-        );
-        self.set_meta_informations(functions, registry);
+        let hash = *hashes
+            .get(&self.name())
+            .expect("hashes must cover every function in `functions`");
+
+        if let Some((cfg_regular, meta)) = cache.get_analyze(&self.name(), hash) {
+            self.cfg_regular = cfg_regular.clone();
+            self.apply_meta_informations(meta.clone());
+            self.content_hash = Some(hash);
+            return;
+        }
+
+        self.cfg_regular
+            .analyze(&self.statements, functions, registry, self.name());
+        let meta = self.compute_meta_informations(functions, registry);
+        self.apply_meta_informations(meta.clone());
+        cache.insert_analyze(self.name(), hash, self.cfg_regular.clone(), meta);
+        self.content_hash = Some(hash);
     }
 
-    /// Set the meta informations such as storage variables read, storage variables written, core function called
-    /// private function called, events emitted
-    fn set_meta_informations(
-        &mut self,
+    /// Compute the meta informations such as storage variables read, storage variables written,
+    /// core function called, private function called, events emitted
+    fn compute_meta_informations(
+        &self,
         functions: &[Function],
         registry: &ProgramRegistry<CoreType, CoreLibfunc>,
-    ) {
+    ) -> MetaInformations {
+        let mut meta = MetaInformations::default();
         for s in self.statements.iter() {
             if let GenStatement::Invocation(invoc) = s {
                 let lib_func = registry
@@ -212,40 +272,101 @@ impl Function {
                             match function.ty() {
                                 Type::Storage => {
                                     if function_name.ends_with("read") {
-                                        self.storage_vars_read.push(s.clone());
+                                        meta.storage_vars_read.push(s.clone());
                                     } else if function_name.ends_with("write") {
-                                        self.storage_vars_written.push(s.clone());
+                                        meta.storage_vars_written.push(s.clone());
                                     }
                                 }
-                                Type::Event => self.events_emitted.push(s.clone()),
-                                Type::Core => self.core_functions_calls.push(s.clone()),
-                                Type::Private => self.private_functions_calls.push(s.clone()),
+                                Type::Event => meta.events_emitted.push(s.clone()),
+                                Type::Core => meta.core_functions_calls.push(s.clone()),
+                                Type::Private => meta.private_functions_calls.push(s.clone()),
                                 Type::AbiCallContract => {
-                                    self.external_functions_calls.push(s.clone())
+                                    meta.external_functions_calls.push(s.clone())
                                 }
                                 Type::AbiLibraryCall => {
-                                    self.library_functions_calls.push(s.clone())
+                                    meta.library_functions_calls.push(s.clone())
                                 }
                                 _ => (),
                             }
                             break;
                         }
                     }
+                } else if let CoreConcreteLibfunc::StarkNet(syscall) = lib_func {
+                    // Storage/event access and calls issued through a raw syscall rather than a
+                    // compiler-generated `Type::Storage`/`Type::Event` wrapper or an ABI trait
+                    // call; without this, those surfaces are invisible to the reentrancy analysis.
+                    match syscall {
+                        StarkNetConcreteLibfunc::StorageRead(_) => {
+                            meta.storage_vars_read.push(s.clone())
+                        }
+                        StarkNetConcreteLibfunc::StorageWrite(_) => {
+                            meta.storage_vars_written.push(s.clone())
+                        }
+                        StarkNetConcreteLibfunc::EmitEvent(_) => {
+                            meta.events_emitted.push(s.clone())
+                        }
+                        StarkNetConcreteLibfunc::CallContract(_) => {
+                            meta.raw_external_functions_calls.push(s.clone())
+                        }
+                        StarkNetConcreteLibfunc::LibraryCall(_) => {
+                            meta.raw_library_functions_calls.push(s.clone())
+                        }
+                        // `GetExecutionInfo` and the remaining syscall wrappers don't feed any
+                        // of the detector-facing buckets above
+                        _ => (),
+                    }
                 }
             }
         }
+        meta
     }
 
+    fn apply_meta_informations(&mut self, meta: MetaInformations) {
+        self.storage_vars_read = meta.storage_vars_read;
+        self.storage_vars_written = meta.storage_vars_written;
+        self.core_functions_calls = meta.core_functions_calls;
+        self.private_functions_calls = meta.private_functions_calls;
+        self.events_emitted = meta.events_emitted;
+        self.external_functions_calls = meta.external_functions_calls;
+        self.library_functions_calls = meta.library_functions_calls;
+        self.raw_external_functions_calls = meta.raw_external_functions_calls;
+        self.raw_library_functions_calls = meta.raw_library_functions_calls;
+    }
+
+    /// Run (or reuse from `cache`) this function's dataflow analyses. Requires `analyze` to have
+    /// run first so `content_hash` is set.
     pub fn run_analyses(
         &mut self,
         functions: &[Function],
         registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+        cache: &mut QueryCache,
     ) {
+        let hash = self
+            .content_hash
+            .expect("analyze must run before run_analyses");
+
+        if let Some(analyses) = cache.get_analyses(&self.name(), hash) {
+            self.analyses = analyses.clone();
+            return;
+        }
+
         if self.ty.unwrap() == Type::External {
             let mut reentrancy = Engine::new(&self.cfg_regular, ReentrancyAnalysis);
             reentrancy.run_analysis(functions, registry);
             self.analyses.reentrancy = reentrancy.result().clone();
         }
+
+        if matches!(self.ty.unwrap(), Type::External | Type::L1Handler) {
+            // `cache.taint_summaries` computes the whole-call-graph fixpoint once and reuses it
+            // for every `External`/`L1Handler` function's `TaintAnalysis`, rather than redoing the
+            // O(N) summary pass each of them would otherwise trigger on construction.
+            let summaries = cache.taint_summaries(functions, registry).clone();
+            let mut taint = Engine::new(&self.cfg_regular, TaintAnalysis::new(self, summaries));
+            taint.run_analysis(functions, registry);
+            self.analyses.taint = taint.result().clone();
+        }
+
+        cache.insert_analyses(&self.name(), hash, self.analyses.clone());
     }
 
     pub(super) fn set_ty(&mut self, ty: Type) {
@@ -0,0 +1,4 @@
+pub mod cfg;
+pub mod core_unit;
+pub mod function;
+pub(crate) mod query;
@@ -0,0 +1,338 @@
+//! Memoization layer for [`Function::analyze`](super::function::Function::analyze) and
+//! [`Function::run_analyses`](super::function::Function::run_analyses), following the rustc move
+//! from ad-hoc metadata methods to memoized queries: re-running Caracal on an edited contract
+//! should only recompute the functions whose Sierra statements (or whose callees) actually
+//! changed, rather than the whole call graph.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use cairo_lang_sierra::extensions::core::{CoreConcreteLibfunc, CoreLibfunc, CoreType};
+use cairo_lang_sierra::program::{GenStatement, Statement as SierraStatement};
+use cairo_lang_sierra::program_registry::ProgramRegistry;
+
+use super::cfg::CfgRegular;
+use super::function::{Analyses, Function, MetaInformations};
+use crate::analysis::taint;
+
+/// Content hash of a function: its own statements plus, transitively, the hashes of everything it
+/// calls. Hashing the callees' hashes rather than their names is what makes invalidation
+/// propagate up the call graph when a dependency changes.
+pub type ContentHash = u64;
+
+fn statement_hash(statements: &[SierraStatement]) -> ContentHash {
+    let mut hasher = DefaultHasher::new();
+    for statement in statements {
+        format!("{statement:?}").hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+/// Compute a stable content hash for every function in `functions` in a single pass, independent
+/// of the order functions happen to be analyzed in (unlike keying a caller's hash off a callee's
+/// possibly-not-yet-computed `Function::content_hash`, which only works callee-before-caller).
+///
+/// Mutually recursive functions form a strongly connected component of the call graph and are
+/// hashed as one unit: none of their individual hashes can be computed before the others', so a
+/// change to any member invalidates every member.
+pub fn compute_content_hashes(
+    functions: &[Function],
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+) -> HashMap<String, ContentHash> {
+    let callees = callee_graph(functions, registry);
+    let names: Vec<String> = functions.iter().map(|f| f.name()).collect();
+    // Tarjan yields components in reverse topological order: a component is only emitted once
+    // every node it can reach has already been explored, so by the time we hash component `i`
+    // every *external* callee it reaches already has a hash in `scc_hash`.
+    let sccs = tarjan_scc(&names, &callees);
+
+    let function_to_scc: HashMap<&str, usize> = sccs
+        .iter()
+        .enumerate()
+        .flat_map(|(i, scc)| scc.iter().map(move |name| (name.as_str(), i)))
+        .collect();
+
+    let mut scc_hash: HashMap<usize, ContentHash> = HashMap::new();
+    for (i, scc) in sccs.iter().enumerate() {
+        let members: Vec<&Function> = scc
+            .iter()
+            .filter_map(|name| functions.iter().find(|f| &f.name() == name))
+            .collect();
+
+        let mut own_hashes: Vec<ContentHash> =
+            members.iter().map(|f| statement_hash(f.get_statements())).collect();
+        own_hashes.sort_unstable();
+
+        let member_names: HashSet<&str> = scc.iter().map(|n| n.as_str()).collect();
+        let mut external_hashes: Vec<ContentHash> = scc
+            .iter()
+            .flat_map(|name| callees.get(name).into_iter().flatten())
+            .filter(|callee| !member_names.contains(callee.as_str()))
+            .filter_map(|callee| function_to_scc.get(callee.as_str()))
+            .filter_map(|scc_id| scc_hash.get(scc_id))
+            .copied()
+            .collect();
+        external_hashes.sort_unstable();
+
+        let mut hasher = DefaultHasher::new();
+        own_hashes.hash(&mut hasher);
+        external_hashes.hash(&mut hasher);
+        scc_hash.insert(i, hasher.finish());
+    }
+
+    functions
+        .iter()
+        .map(|f| {
+            let hash = function_to_scc
+                .get(f.name().as_str())
+                .and_then(|scc_id| scc_hash.get(scc_id))
+                .copied()
+                .unwrap_or_else(|| statement_hash(f.get_statements()));
+            (f.name(), hash)
+        })
+        .collect()
+}
+
+/// Names of the functions each function directly calls, resolved through the registry (only
+/// `CoreConcreteLibfunc::FunctionCall`s into another function we know about count as an edge)
+fn callee_graph(
+    functions: &[Function],
+    registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+) -> HashMap<String, Vec<String>> {
+    let names: HashSet<String> = functions.iter().map(|f| f.name()).collect();
+    functions
+        .iter()
+        .map(|f| {
+            let mut callees = Vec::new();
+            for s in f.get_statements() {
+                if let GenStatement::Invocation(invoc) = s {
+                    if let Ok(CoreConcreteLibfunc::FunctionCall(f_called)) =
+                        registry.get_libfunc(&invoc.libfunc_id)
+                    {
+                        let callee_name =
+                            f_called.function.id.debug_name.as_ref().unwrap().to_string();
+                        if names.contains(&callee_name) {
+                            callees.push(callee_name);
+                        }
+                    }
+                }
+            }
+            callees.sort_unstable();
+            callees.dedup();
+            (f.name(), callees)
+        })
+        .collect()
+}
+
+/// Tarjan's strongly-connected-components algorithm over the call graph, returning components in
+/// reverse topological order (a function's callees' components come out before its own).
+fn tarjan_scc(names: &[String], callees: &HashMap<String, Vec<String>>) -> Vec<Vec<String>> {
+    struct Tarjan<'a> {
+        names: &'a [String],
+        callees: &'a HashMap<String, Vec<String>>,
+        index_of: HashMap<&'a str, usize>,
+        index: Vec<Option<usize>>,
+        low_link: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next_index: usize,
+        result: Vec<Vec<String>>,
+    }
+
+    impl<'a> Tarjan<'a> {
+        fn strongconnect(&mut self, v: usize) {
+            self.index[v] = Some(self.next_index);
+            self.low_link[v] = self.next_index;
+            self.next_index += 1;
+            self.stack.push(v);
+            self.on_stack[v] = true;
+
+            if let Some(callee_names) = self.callees.get(&self.names[v]) {
+                for callee_name in callee_names {
+                    let Some(&w) = self.index_of.get(callee_name.as_str()) else {
+                        continue;
+                    };
+                    if self.index[w].is_none() {
+                        self.strongconnect(w);
+                        self.low_link[v] = self.low_link[v].min(self.low_link[w]);
+                    } else if self.on_stack[w] {
+                        self.low_link[v] = self.low_link[v].min(self.index[w].unwrap());
+                    }
+                }
+            }
+
+            if self.low_link[v] == self.index[v].unwrap() {
+                let mut component = Vec::new();
+                loop {
+                    let w = self.stack.pop().unwrap();
+                    self.on_stack[w] = false;
+                    component.push(self.names[w].clone());
+                    if w == v {
+                        break;
+                    }
+                }
+                self.result.push(component);
+            }
+        }
+    }
+
+    let index_of: HashMap<&str, usize> = names.iter().enumerate().map(|(i, n)| (n.as_str(), i)).collect();
+    let n = names.len();
+
+    let mut tarjan = Tarjan {
+        names,
+        callees,
+        index_of,
+        index: vec![None; n],
+        low_link: vec![0; n],
+        on_stack: vec![false; n],
+        stack: Vec::new(),
+        next_index: 0,
+        result: Vec::new(),
+    };
+
+    for v in 0..n {
+        if tarjan.index[v].is_none() {
+            tarjan.strongconnect(v);
+        }
+    }
+
+    tarjan.result
+}
+
+#[derive(Clone, Default)]
+struct CachedFunction {
+    hash: ContentHash,
+    cfg_regular: CfgRegular,
+    meta: MetaInformations,
+    /// `None` until `run_analyses` has run at least once for this hash
+    analyses: Option<Analyses>,
+}
+
+/// Per-function cache of `CfgRegular`, meta-information and `Analyses`, keyed by [`ContentHash`].
+/// Owned by whatever drives a Caracal run and threaded into `Function::analyze`/`run_analyses`.
+/// The LSP server does not keep one across edits: it builds a new `CoreUnit` on every
+/// `didOpen`/`didSave`.
+#[derive(Default)]
+pub struct QueryCache {
+    by_function: HashMap<String, CachedFunction>,
+    /// Whole-call-graph taint summary (see `taint::build_summaries`), computed at most once per
+    /// `QueryCache` and shared by every function's `run_analyses` rather than recomputed on each
+    /// `TaintAnalysis` construction.
+    taint_summaries: Option<HashMap<String, bool>>,
+}
+
+impl QueryCache {
+    pub fn new() -> Self {
+        QueryCache::default()
+    }
+
+    /// Cached CFG and meta-information for `name`, if its content hash still matches
+    pub fn get_analyze(&self, name: &str, hash: ContentHash) -> Option<(&CfgRegular, &MetaInformations)> {
+        self.by_function
+            .get(name)
+            .filter(|cached| cached.hash == hash)
+            .map(|cached| (&cached.cfg_regular, &cached.meta))
+    }
+
+    pub fn insert_analyze(
+        &mut self,
+        name: String,
+        hash: ContentHash,
+        cfg_regular: CfgRegular,
+        meta: MetaInformations,
+    ) {
+        self.by_function.insert(
+            name,
+            CachedFunction {
+                hash,
+                cfg_regular,
+                meta,
+                analyses: None,
+            },
+        );
+    }
+
+    /// Cached `Analyses` for `name`, if its content hash still matches and analyses have run
+    pub fn get_analyses(&self, name: &str, hash: ContentHash) -> Option<&Analyses> {
+        self.by_function
+            .get(name)
+            .filter(|cached| cached.hash == hash)
+            .and_then(|cached| cached.analyses.as_ref())
+    }
+
+    pub fn insert_analyses(&mut self, name: &str, hash: ContentHash, analyses: Analyses) {
+        if let Some(cached) = self.by_function.get_mut(name) {
+            if cached.hash == hash {
+                cached.analyses = Some(analyses);
+            }
+        }
+    }
+
+    /// The whole-program taint summary, computing it on first use and reusing it for the rest of
+    /// this `QueryCache`'s lifetime.
+    pub fn taint_summaries(
+        &mut self,
+        functions: &[Function],
+        registry: &ProgramRegistry<CoreType, CoreLibfunc>,
+    ) -> &HashMap<String, bool> {
+        self.taint_summaries
+            .get_or_insert_with(|| taint::build_summaries(functions, registry))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn callees_of(pairs: &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+        pairs
+            .iter()
+            .map(|(name, callees)| {
+                (
+                    name.to_string(),
+                    callees.iter().map(|c| c.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tarjan_scc_linear_chain_has_no_cycles() {
+        let names: Vec<String> = ["a", "b", "c"].iter().map(|s| s.to_string()).collect();
+        let callees = callees_of(&[("a", &["b"]), ("b", &["c"]), ("c", &[])]);
+
+        let sccs = tarjan_scc(&names, &callees);
+
+        assert!(sccs.iter().all(|scc| scc.len() == 1));
+        // Reverse topological order: `c` (no callees) comes out before its callers.
+        let position = |name: &str| sccs.iter().position(|scc| scc[0] == name).unwrap();
+        assert!(position("c") < position("b"));
+        assert!(position("b") < position("a"));
+    }
+
+    #[test]
+    fn tarjan_scc_mutual_recursion_is_one_component() {
+        let names: Vec<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
+        let callees = callees_of(&[("a", &["b"]), ("b", &["a"])]);
+
+        let sccs = tarjan_scc(&names, &callees);
+
+        assert_eq!(sccs.len(), 1);
+        let mut component = sccs[0].clone();
+        component.sort();
+        assert_eq!(component, vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn statement_hash_is_stable_and_sensitive_to_content() {
+        let empty = statement_hash(&[]);
+        let one_return = statement_hash(&[SierraStatement::Return(vec![])]);
+
+        // Same input hashes the same way every time.
+        assert_eq!(empty, statement_hash(&[]));
+        // Different statements hash differently.
+        assert_ne!(empty, one_return);
+    }
+}
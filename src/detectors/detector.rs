@@ -0,0 +1,48 @@
+use crate::core::core_unit::CoreUnit;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// Severity of a finding, used both for display and to pick a SARIF `level`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Impact {
+    High,
+    Medium,
+    Low,
+    Informational,
+}
+
+/// How confident the detector is that a finding is a true positive
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Confidence {
+    High,
+    Medium,
+    Low,
+}
+
+/// A single finding reported by a detector
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Result {
+    /// Id of the detector that produced this finding, e.g. "reentrancy"
+    pub id: String,
+    pub impact: Impact,
+    pub confidence: Confidence,
+    pub message: String,
+    /// `Function::name()` of the function the finding was found in
+    pub function: String,
+}
+
+pub trait Detector {
+    /// Unique id of the detector, used as the SARIF `ruleId` and the JSON `id`
+    fn id(&self) -> &str;
+    fn name(&self) -> &str;
+    fn description(&self) -> &str;
+    fn impact(&self) -> Impact;
+    fn confidence(&self) -> Confidence;
+    fn run(&self, core: &CoreUnit) -> Vec<Result>;
+}
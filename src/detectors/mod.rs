@@ -0,0 +1,11 @@
+pub mod detector;
+#[cfg(feature = "serde")]
+pub mod sarif;
+pub mod tainted_sink;
+
+use detector::Detector;
+
+/// All the built-in detectors, in the order they're run
+pub fn get_detectors() -> Vec<Box<dyn Detector>> {
+    vec![Box::new(tainted_sink::TaintedSink)]
+}
@@ -0,0 +1,159 @@
+//! Serialization of [`Result`](crate::detectors::detector::Result) as SARIF 2.1.0, the format
+//! consumed by GitHub code scanning and most CI dashboards.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::detectors::detector::{Confidence, Impact, Result};
+
+const SARIF_SCHEMA: &str = "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+#[derive(Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Serialize)]
+struct SarifDriver {
+    name: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Serialize)]
+struct SarifRule {
+    id: String,
+}
+
+#[derive(Serialize)]
+struct SarifResult {
+    #[serde(rename = "ruleId")]
+    rule_id: String,
+    level: &'static str,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+    #[serde(rename = "logicalLocations")]
+    logical_locations: Vec<SarifLogicalLocation>,
+}
+
+#[derive(Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize)]
+struct SarifLocation {
+    #[serde(rename = "physicalLocation")]
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Serialize)]
+struct SarifPhysicalLocation {
+    #[serde(rename = "artifactLocation")]
+    artifact_location: SarifArtifactLocation,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+/// Points at the function a finding was found in. Caracal doesn't carry a Sierra-statement to
+/// Cairo-source location map yet, so this is the best available anchor; it belongs in
+/// `logicalLocations`, not `physicalLocation.artifactLocation.uri` (that field is a file/artifact
+/// URI and GitHub code scanning rejects or mis-anchors results that put something else there).
+#[derive(Serialize)]
+struct SarifLogicalLocation {
+    #[serde(rename = "fullyQualifiedName")]
+    fully_qualified_name: String,
+    kind: &'static str,
+}
+
+/// SARIF only has `error`/`warning`/`note`/`none` levels, so `Impact` collapses onto them;
+/// `Confidence` has no SARIF equivalent and is folded into the message text instead.
+fn level_for(impact: Impact) -> &'static str {
+    match impact {
+        Impact::High => "error",
+        Impact::Medium => "warning",
+        Impact::Low | Impact::Informational => "note",
+    }
+}
+
+fn message_for(result: &Result) -> String {
+    match result.confidence {
+        Confidence::High => result.message.clone(),
+        confidence => format!("{} (confidence: {:?})", result.message, confidence),
+    }
+}
+
+/// Build a SARIF log with a single run from a batch of detector findings. `artifact` is the
+/// analyzed file every result's physical location points at — until Caracal carries a Sierra to
+/// Cairo source map, that's the most precise location available; the finding's function is
+/// reported in `logicalLocations` instead of overloading the artifact URI.
+pub fn to_sarif(results: &[Result], artifact: &Path) -> SarifLog {
+    let mut rule_ids: Vec<String> = results.iter().map(|r| r.id.clone()).collect();
+    rule_ids.sort();
+    rule_ids.dedup();
+
+    let artifact_uri = artifact.to_string_lossy().into_owned();
+
+    SarifLog {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![SarifRun {
+            tool: SarifTool {
+                driver: SarifDriver {
+                    name: "caracal",
+                    rules: rule_ids.into_iter().map(|id| SarifRule { id }).collect(),
+                },
+            },
+            results: results
+                .iter()
+                .map(|r| SarifResult {
+                    rule_id: r.id.clone(),
+                    level: level_for(r.impact),
+                    message: SarifMessage {
+                        text: message_for(r),
+                    },
+                    locations: vec![SarifLocation {
+                        physical_location: SarifPhysicalLocation {
+                            artifact_location: SarifArtifactLocation {
+                                uri: artifact_uri.clone(),
+                            },
+                        },
+                    }],
+                    logical_locations: vec![SarifLogicalLocation {
+                        fully_qualified_name: r.function.clone(),
+                        kind: "function",
+                    }],
+                })
+                .collect(),
+        }],
+    }
+}
+
+/// Serialize detector findings directly as a JSON array, for consumers that don't want SARIF
+pub fn to_json(results: &[Result]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(results)
+}
+
+impl SarifLog {
+    pub fn to_json_string(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
@@ -0,0 +1,56 @@
+use crate::analysis::taint;
+use crate::core::core_unit::CoreUnit;
+use crate::core::function::Type;
+
+use super::detector::{Confidence, Detector, Impact, Result};
+
+/// Flags a storage write or an external/library call whose arguments include data traceable back
+/// to a function parameter or an `AbiCallContract` call result, without first passing through a
+/// sanitizing assert/range-check. See [`crate::analysis::taint`] for how taint is tracked.
+pub struct TaintedSink;
+
+impl Detector for TaintedSink {
+    fn id(&self) -> &str {
+        "tainted-sink"
+    }
+
+    fn name(&self) -> &str {
+        "Tainted storage write or external call"
+    }
+
+    fn description(&self) -> &str {
+        "Unsanitized caller- or callee-controlled data reaches a storage write or an \
+         external/library call"
+    }
+
+    fn impact(&self) -> Impact {
+        Impact::Medium
+    }
+
+    fn confidence(&self) -> Confidence {
+        Confidence::Medium
+    }
+
+    fn run(&self, core: &CoreUnit) -> Vec<Result> {
+        let functions = core.functions();
+        let registry = core.registry();
+
+        functions
+            .iter()
+            .filter(|f| matches!(f.ty(), Type::External | Type::L1Handler))
+            .flat_map(|f| {
+                taint::tainted_sinks(f, functions, registry)
+                    .into_iter()
+                    .map(|_| Result {
+                        id: self.id().to_string(),
+                        impact: self.impact(),
+                        confidence: self.confidence(),
+                        message: "tainted value reaches a storage write or external/library call \
+                                  without passing through an assert or range-check"
+                            .to_string(),
+                        function: f.name(),
+                    })
+            })
+            .collect()
+    }
+}
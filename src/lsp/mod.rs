@@ -0,0 +1,179 @@
+//! `caracal server` — a long-running LSP server so editors get detector findings as
+//! `textDocument/publishDiagnostics` on save, instead of a one-shot CLI run.
+
+use std::collections::HashSet;
+use std::error::Error;
+
+use lsp_server::{Connection, Message, Notification};
+use lsp_types::notification::{
+    DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument, Notification as _,
+    PublishDiagnostics,
+};
+use lsp_types::{
+    Diagnostic, DiagnosticSeverity, Position, PublishDiagnosticsParams, Range,
+    ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, TextDocumentSyncOptions,
+    TextDocumentSyncSaveOptions, Url,
+};
+
+use crate::core::core_unit::{CoreOpts, CoreUnit};
+use crate::detectors::detector::{Impact, Result as DetectorResult};
+use crate::detectors::get_detectors;
+
+/// State for a single editor session: the corelib path is fixed at startup, the set of open
+/// files changes as the editor opens/closes buffers.
+pub struct LspServer {
+    corelib: Option<std::path::PathBuf>,
+    /// Files we've published diagnostics for, so `didClose` knows which ones to clear
+    open_documents: HashSet<Url>,
+}
+
+impl LspServer {
+    pub fn new(corelib: Option<std::path::PathBuf>) -> Self {
+        LspServer {
+            corelib,
+            open_documents: HashSet::new(),
+        }
+    }
+
+    /// Start the server on stdio and block until the client disconnects
+    pub fn run(mut self) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let (connection, io_threads) = Connection::stdio();
+
+        // We always re-read the file from disk rather than from the editor's in-memory buffer
+        // (see `analyze_and_publish`), so we don't need the client to stream buffer contents on
+        // every keystroke — only that it tells us about open/close/save.
+        let capabilities = ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Options(
+                TextDocumentSyncOptions {
+                    open_close: Some(true),
+                    change: Some(TextDocumentSyncKind::NONE),
+                    save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                    ..Default::default()
+                },
+            )),
+            ..Default::default()
+        };
+        connection.initialize(serde_json::to_value(capabilities)?)?;
+
+        for msg in &connection.receiver {
+            match msg {
+                Message::Notification(notification) => {
+                    self.handle_notification(&connection, notification)?;
+                }
+                Message::Request(req) if connection.handle_shutdown(&req)? => break,
+                _ => {}
+            }
+        }
+
+        io_threads.join()?;
+        Ok(())
+    }
+
+    fn handle_notification(
+        &mut self,
+        connection: &Connection,
+        notification: Notification,
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: lsp_types::DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                self.open_documents.insert(params.text_document.uri.clone());
+                self.analyze_and_publish(connection, &params.text_document.uri)?;
+            }
+            DidSaveTextDocument::METHOD => {
+                let params: lsp_types::DidSaveTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                self.analyze_and_publish(connection, &params.text_document.uri)?;
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: lsp_types::DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                if self.open_documents.remove(&params.text_document.uri) {
+                    self.publish_diagnostics(connection, &params.text_document.uri, Vec::new())?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Rebuild the `CoreUnit` for a single file from disk and re-run the detectors against it.
+    /// This is the incremental entry point the server loop drives per-file, as opposed to
+    /// `main`'s one-shot whole-project run. Driven from `didOpen`/`didSave` only: we don't
+    /// subscribe to `didChange`, so we never have to reconcile an unsaved in-memory buffer
+    /// against what's on disk.
+    fn analyze_and_publish(
+        &self,
+        connection: &Connection,
+        uri: &Url,
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let target = uri
+            .to_file_path()
+            .map_err(|_| "document URI is not a local file")?;
+
+        let diagnostics = match CoreUnit::new(CoreOpts {
+            target,
+            corelib: self.corelib.clone(),
+        }) {
+            Ok(core) => get_detectors()
+                .iter()
+                .flat_map(|d| d.run(&core))
+                .map(result_to_diagnostic)
+                .collect(),
+            // A file mid-edit may not parse; report it like any other diagnostic rather than
+            // dropping the request.
+            Err(err) => vec![Diagnostic {
+                range: Range::new(Position::new(0, 0), Position::new(0, 1)),
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("caracal".to_string()),
+                message: err.to_string(),
+                ..Default::default()
+            }],
+        };
+
+        self.publish_diagnostics(connection, uri, diagnostics)
+    }
+
+    fn publish_diagnostics(
+        &self,
+        connection: &Connection,
+        uri: &Url,
+        diagnostics: Vec<Diagnostic>,
+    ) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let params = PublishDiagnosticsParams {
+            uri: uri.clone(),
+            diagnostics,
+            version: None,
+        };
+        connection
+            .sender
+            .send(Message::Notification(Notification::new(
+                PublishDiagnostics::METHOD.to_string(),
+                params,
+            )))?;
+        Ok(())
+    }
+}
+
+/// Map a detector finding onto an LSP diagnostic. Caracal doesn't yet carry a Sierra-statement to
+/// Cairo-source location map, so findings are anchored to the start of the file; the function
+/// name that produced the finding is kept in the message instead of a precise range.
+fn result_to_diagnostic(result: DetectorResult) -> Diagnostic {
+    Diagnostic {
+        range: Range::new(Position::new(0, 0), Position::new(0, 1)),
+        severity: Some(severity_for(result.impact)),
+        source: Some("caracal".to_string()),
+        message: format!("[{}] {} (in {})", result.id, result.message, result.function),
+        ..Default::default()
+    }
+}
+
+fn severity_for(impact: Impact) -> DiagnosticSeverity {
+    match impact {
+        Impact::High => DiagnosticSeverity::ERROR,
+        Impact::Medium => DiagnosticSeverity::WARNING,
+        Impact::Low => DiagnosticSeverity::INFORMATION,
+        Impact::Informational => DiagnosticSeverity::HINT,
+    }
+}